@@ -739,6 +739,9 @@ pub(crate) fn get_op(name: &str) -> Option<&'static Op> {
         "unpack_bits" => &OP_UNPACK_BITS,
         "concat" => &OP_CONCAT,
         "str_includes" => &OP_STR_INCLUDES,
+        "split" => &OP_SPLIT,
+        "join" => &OP_JOIN,
+        "format" => &OP_FORMAT,
         "lowercase" => &OP_LOWERCASE,
         "uppercase" => &OP_UPPERCASE,
         "trim" => &OP_TRIM,
@@ -753,6 +756,7 @@ pub(crate) fn get_op(name: &str) -> Option<&'static Op> {
         "is_string" => &OP_IS_STRING,
         "is_list" => &OP_IS_LIST,
         "is_bytes" => &OP_IS_BYTES,
+        "typeof" => &OP_TYPEOF,
         "is_in" => &OP_IS_IN,
         "is_finite" => &OP_IS_FINITE,
         "is_infinite" => &OP_IS_INFINITE,
@@ -761,6 +765,7 @@ pub(crate) fn get_op(name: &str) -> Option<&'static Op> {
         "length" => &OP_LENGTH,
         "sorted" => &OP_SORTED,
         "reverse" => &OP_REVERSE,
+        "hash" => &OP_HASH,
         "append" => &OP_APPEND,
         "prepend" => &OP_PREPEND,
         "unicode_normalize" => &OP_UNICODE_NORMALIZE,
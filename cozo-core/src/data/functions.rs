@@ -6,7 +6,7 @@
  * You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::collections::BTreeSet;
 use std::ops::{Div, Rem};
 use std::str::FromStr;
@@ -27,6 +27,7 @@ use uuid::v1::Timestamp;
 
 use crate::data::expr::Op;
 use crate::data::json::JsonValue;
+use crate::data::memcmp::MemCmpEncoder;
 use crate::data::value::{DataValue, Num, RegexWrapper, UuidWrapper, Validity, ValidityTs};
 
 macro_rules! define_op {
@@ -110,12 +111,26 @@ pub(crate) fn op_neq(args: &[DataValue]) -> Result<DataValue> {
     }))
 }
 
+// NaN is placed as in the total ordering used for stored keys: after every number,
+// or before every number if its sign bit is set
+fn cmp_float_int(f: f64, i: i64) -> Ordering {
+    match f.partial_cmp(&(i as f64)) {
+        Some(ord) => ord,
+        None if f.is_sign_negative() => Ordering::Less,
+        None => Ordering::Greater,
+    }
+}
+
 define_op!(OP_GT, 2, false);
 pub(crate) fn op_gt(args: &[DataValue]) -> Result<DataValue> {
     ensure_same_value_type(&args[0], &args[1])?;
     Ok(DataValue::from(match (&args[0], &args[1]) {
-        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => *l > *r as f64,
-        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => *l as f64 > *r,
+        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => {
+            cmp_float_int(*l, *r) == Ordering::Greater
+        }
+        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => {
+            cmp_float_int(*r, *l) == Ordering::Less
+        }
         (a, b) => a > b,
     }))
 }
@@ -124,8 +139,12 @@ define_op!(OP_GE, 2, false);
 pub(crate) fn op_ge(args: &[DataValue]) -> Result<DataValue> {
     ensure_same_value_type(&args[0], &args[1])?;
     Ok(DataValue::from(match (&args[0], &args[1]) {
-        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => *l >= *r as f64,
-        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => *l as f64 >= *r,
+        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => {
+            cmp_float_int(*l, *r) != Ordering::Less
+        }
+        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => {
+            cmp_float_int(*r, *l) != Ordering::Greater
+        }
         (a, b) => a >= b,
     }))
 }
@@ -134,8 +153,12 @@ define_op!(OP_LT, 2, false);
 pub(crate) fn op_lt(args: &[DataValue]) -> Result<DataValue> {
     ensure_same_value_type(&args[0], &args[1])?;
     Ok(DataValue::from(match (&args[0], &args[1]) {
-        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => *l < (*r as f64),
-        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => (*l as f64) < *r,
+        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => {
+            cmp_float_int(*l, *r) == Ordering::Less
+        }
+        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => {
+            cmp_float_int(*r, *l) == Ordering::Greater
+        }
         (a, b) => a < b,
     }))
 }
@@ -144,8 +167,12 @@ define_op!(OP_LE, 2, false);
 pub(crate) fn op_le(args: &[DataValue]) -> Result<DataValue> {
     ensure_same_value_type(&args[0], &args[1])?;
     Ok(DataValue::from(match (&args[0], &args[1]) {
-        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => *l <= (*r as f64),
-        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => (*l as f64) <= *r,
+        (DataValue::Num(Num::Float(l)), DataValue::Num(Num::Int(r))) => {
+            cmp_float_int(*l, *r) != Ordering::Greater
+        }
+        (DataValue::Num(Num::Int(l)), DataValue::Num(Num::Float(r))) => {
+            cmp_float_int(*r, *l) != Ordering::Less
+        }
         (a, b) => a <= b,
     }))
 }
@@ -156,7 +183,11 @@ pub(crate) fn op_add(args: &[DataValue]) -> Result<DataValue> {
     let mut f_accum = 0.0f64;
     for arg in args {
         match arg {
-            DataValue::Num(Num::Int(i)) => i_accum += i,
+            DataValue::Num(Num::Int(i)) => {
+                i_accum = i_accum
+                    .checked_add(*i)
+                    .ok_or_else(|| miette!("integer overflow in addition"))?
+            }
             DataValue::Num(Num::Float(f)) => f_accum += f,
             _ => bail!("addition requires numbers"),
         }
@@ -201,9 +232,10 @@ pub(crate) fn op_min(args: &[DataValue]) -> Result<DataValue> {
 define_op!(OP_SUB, 2, false);
 pub(crate) fn op_sub(args: &[DataValue]) -> Result<DataValue> {
     Ok(match (&args[0], &args[1]) {
-        (DataValue::Num(Num::Int(a)), DataValue::Num(Num::Int(b))) => {
-            DataValue::Num(Num::Int(*a - *b))
-        }
+        (DataValue::Num(Num::Int(a)), DataValue::Num(Num::Int(b))) => DataValue::Num(Num::Int(
+            a.checked_sub(*b)
+                .ok_or_else(|| miette!("integer overflow in subtraction"))?,
+        )),
         (DataValue::Num(Num::Float(a)), DataValue::Num(Num::Float(b))) => {
             DataValue::Num(Num::Float(*a - *b))
         }
@@ -223,7 +255,11 @@ pub(crate) fn op_mul(args: &[DataValue]) -> Result<DataValue> {
     let mut f_accum = 1.0f64;
     for arg in args {
         match arg {
-            DataValue::Num(Num::Int(i)) => i_accum *= i,
+            DataValue::Num(Num::Int(i)) => {
+                i_accum = i_accum
+                    .checked_mul(*i)
+                    .ok_or_else(|| miette!("integer overflow in multiplication"))?
+            }
             DataValue::Num(Num::Float(f)) => f_accum *= f,
             _ => bail!("multiplication requires numbers"),
         }
@@ -257,7 +293,10 @@ pub(crate) fn op_div(args: &[DataValue]) -> Result<DataValue> {
 define_op!(OP_MINUS, 1, false);
 pub(crate) fn op_minus(args: &[DataValue]) -> Result<DataValue> {
     Ok(match &args[0] {
-        DataValue::Num(Num::Int(i)) => DataValue::Num(Num::Int(-(*i))),
+        DataValue::Num(Num::Int(i)) => DataValue::Num(Num::Int(
+            i.checked_neg()
+                .ok_or_else(|| miette!("integer overflow in negation"))?,
+        )),
         DataValue::Num(Num::Float(f)) => DataValue::Num(Num::Float(-(*f))),
         _ => bail!("minus can only be applied to numbers"),
     })
@@ -266,7 +305,10 @@ pub(crate) fn op_minus(args: &[DataValue]) -> Result<DataValue> {
 define_op!(OP_ABS, 1, false);
 pub(crate) fn op_abs(args: &[DataValue]) -> Result<DataValue> {
     Ok(match &args[0] {
-        DataValue::Num(Num::Int(i)) => DataValue::Num(Num::Int(i.abs())),
+        DataValue::Num(Num::Int(i)) => DataValue::Num(Num::Int(
+            i.checked_abs()
+                .ok_or_else(|| miette!("integer overflow in 'abs'"))?,
+        )),
         DataValue::Num(Num::Float(f)) => DataValue::Num(Num::Float(f.abs())),
         _ => bail!("'abs' requires numbers"),
     })
@@ -523,7 +565,13 @@ define_op!(OP_MOD, 2, false);
 pub(crate) fn op_mod(args: &[DataValue]) -> Result<DataValue> {
     Ok(match (&args[0], &args[1]) {
         (DataValue::Num(Num::Int(a)), DataValue::Num(Num::Int(b))) => {
-            DataValue::Num(Num::Int(a.rem(b)))
+            DataValue::Num(Num::Int(a.checked_rem(*b).ok_or_else(|| {
+                if *b == 0 {
+                    miette!("division by zero in modulo")
+                } else {
+                    miette!("integer overflow in modulo")
+                }
+            })?))
         }
         (DataValue::Num(Num::Float(a)), DataValue::Num(Num::Float(b))) => {
             DataValue::Num(Num::Float(a.rem(*b)))
@@ -739,6 +787,73 @@ pub(crate) fn op_str_includes(args: &[DataValue]) -> Result<DataValue> {
     }
 }
 
+define_op!(OP_SPLIT, 2, false);
+pub(crate) fn op_split(args: &[DataValue]) -> Result<DataValue> {
+    match (&args[0], &args[1]) {
+        (DataValue::Null, _) | (_, DataValue::Null) => Ok(DataValue::Null),
+        (DataValue::Str(s), DataValue::Str(sep)) => {
+            ensure!(!sep.is_empty(), "'split' requires a non-empty separator");
+            Ok(DataValue::List(
+                s.split(sep as &str).map(DataValue::from).collect_vec(),
+            ))
+        }
+        _ => bail!("'split' requires strings"),
+    }
+}
+
+define_op!(OP_JOIN, 2, false);
+pub(crate) fn op_join(args: &[DataValue]) -> Result<DataValue> {
+    match (&args[0], &args[1]) {
+        (DataValue::Null, _) | (_, DataValue::Null) => Ok(DataValue::Null),
+        (DataValue::List(l), DataValue::Str(sep)) => {
+            let parts: Vec<_> = l
+                .iter()
+                .map(|v| {
+                    v.get_str()
+                        .ok_or_else(|| miette!("'join' requires a list of strings"))
+                })
+                .try_collect()?;
+            Ok(DataValue::from(parts.join(sep.as_str())))
+        }
+        _ => bail!("'join' requires a list and a string separator"),
+    }
+}
+
+define_op!(OP_FORMAT, 1, true);
+pub(crate) fn op_format(args: &[DataValue]) -> Result<DataValue> {
+    let template = args[0]
+        .get_str()
+        .ok_or_else(|| miette!("'format' requires a string template"))?;
+    let mut fill_args = args[1..].iter();
+    let mut ret = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('{', Some('{')) | ('}', Some('}')) => {
+                chars.next();
+                ret.push(c);
+            }
+            ('{', Some('}')) => {
+                chars.next();
+                let arg = fill_args
+                    .next()
+                    .ok_or_else(|| miette!("'format' has more placeholders than arguments"))?;
+                match op_to_string(std::slice::from_ref(arg))? {
+                    DataValue::Str(s) => ret.push_str(&s),
+                    _ => unreachable!(),
+                }
+            }
+            ('{', _) | ('}', _) => bail!("'format' template has unmatched brace"),
+            _ => ret.push(c),
+        }
+    }
+    ensure!(
+        fill_args.next().is_none(),
+        "'format' has more arguments than placeholders"
+    );
+    Ok(DataValue::from(ret))
+}
+
 define_op!(OP_LOWERCASE, 1, false);
 pub(crate) fn op_lowercase(args: &[DataValue]) -> Result<DataValue> {
     match &args[0] {
@@ -977,6 +1092,24 @@ pub(crate) fn op_is_bytes(args: &[DataValue]) -> Result<DataValue> {
     Ok(DataValue::from(matches!(args[0], DataValue::Bytes(_))))
 }
 
+define_op!(OP_TYPEOF, 1, false);
+pub(crate) fn op_typeof(args: &[DataValue]) -> Result<DataValue> {
+    Ok(DataValue::from(match &args[0] {
+        DataValue::Null => "Null",
+        DataValue::Bool(_) => "Bool",
+        DataValue::Num(Num::Int(_)) => "Int",
+        DataValue::Num(Num::Float(_)) => "Float",
+        DataValue::Str(_) => "String",
+        DataValue::Bytes(_) => "Bytes",
+        DataValue::Uuid(_) => "Uuid",
+        DataValue::Regex(_) => "Regex",
+        DataValue::List(_) => "List",
+        DataValue::Set(_) => "Set",
+        DataValue::Validity(_) => "Validity",
+        DataValue::Bot => "Bot",
+    }))
+}
+
 define_op!(OP_LENGTH, 1, false);
 pub(crate) fn op_length(args: &[DataValue]) -> Result<DataValue> {
     Ok(DataValue::from(match &args[0] {
@@ -1022,6 +1155,36 @@ pub(crate) fn op_reverse(args: &[DataValue]) -> Result<DataValue> {
     Ok(DataValue::List(arg))
 }
 
+define_op!(OP_HASH, 1, false);
+pub(crate) fn op_hash(args: &[DataValue]) -> Result<DataValue> {
+    // FNV-1a over the memcmp encoding, with every number normalized to its f64 value first:
+    // `==` compares ints with floats through f64, so values comparing equal also hash equal
+    const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut buf = vec![];
+    buf.encode_datavalue(&normalize_for_hash(&args[0]));
+    let mut h = FNV_OFFSET;
+    for b in buf {
+        h ^= b as u64;
+        h = h.wrapping_mul(FNV_PRIME);
+    }
+    Ok(DataValue::from(h as i64))
+}
+
+fn normalize_for_hash(v: &DataValue) -> DataValue {
+    match v {
+        DataValue::Num(n) => {
+            let f = n.get_float();
+            // -0.0 == 0.0, but the two encode differently
+            DataValue::from(if f == 0. { 0. } else { f })
+        }
+        DataValue::List(l) => DataValue::List(l.iter().map(normalize_for_hash).collect()),
+        DataValue::Set(s) => DataValue::Set(s.iter().map(normalize_for_hash).collect()),
+        v => v.clone(),
+    }
+}
+
 define_op!(OP_HAVERSINE, 4, false);
 pub(crate) fn op_haversine(args: &[DataValue]) -> Result<DataValue> {
     let miette = || miette!("'haversine' requires numbers");
@@ -19,7 +19,7 @@ use smartstring::{LazyCompact, SmartString};
 use thiserror::Error;
 
 use crate::data::expr::Expr;
-use crate::data::value::{DataValue, UuidWrapper, Validity, ValidityTs};
+use crate::data::value::{DataValue, Num, UuidWrapper, Validity, ValidityTs};
 
 #[derive(Debug, Clone, Eq, PartialEq, serde_derive::Deserialize, serde_derive::Serialize)]
 pub(crate) struct NullableColType {
@@ -96,6 +96,13 @@ pub(crate) struct StoredRelationMetadata {
 }
 
 impl StoredRelationMetadata {
+    pub(crate) fn column_names(&self) -> String {
+        self.keys
+            .iter()
+            .chain(self.non_keys.iter())
+            .map(|col| &col.name)
+            .join(", ")
+    }
     pub(crate) fn satisfied_by_required_col(&self, col: &ColumnDef, is_key: bool) -> Result<()> {
         let targets = if is_key { &self.keys } else { &self.non_keys };
         for target in targets {
@@ -103,7 +110,8 @@ impl StoredRelationMetadata {
                 return Ok(());
             }
         }
-        if col.default_gen.is_none() {
+        // nullable non-key columns without an explicit default are filled with null
+        if col.default_gen.is_none() && (is_key || !col.typing.nullable) {
             #[derive(Debug, Error, Diagnostic)]
             #[error("required column {0} not provided by input")]
             #[diagnostic(code(eval::required_col_not_provided))]
@@ -138,13 +146,37 @@ impl StoredRelationMetadata {
         #[derive(Debug, Error, Diagnostic)]
         #[error("required column {0} not found")]
         #[diagnostic(code(eval::required_col_not_found))]
-        struct ColumnNotFound(String);
+        struct ColumnNotFound(String, #[help] String);
 
-        bail!(ColumnNotFound(col.name.to_string()))
+        let available = targets.iter().map(|target| &target.name).join(", ");
+        bail!(ColumnNotFound(
+            col.name.to_string(),
+            format!(
+                "Available {} columns are: {}",
+                if is_key { "key" } else { "non-key" },
+                available
+            )
+        ))
     }
 }
 
 impl NullableColType {
+    /// The form `data` takes when encoded in a key of this type. Numbers that compare
+    /// equal under `==` get the same encoding, so key lookups agree with `==`.
+    /// This holds for integers of magnitude below 2^53: beyond that an int and
+    /// a float can compare equal without either being exactly representable as the other.
+    pub(crate) fn canonicalize_key(&self, data: DataValue) -> DataValue {
+        match (&self.coltype, data) {
+            (ColType::Float, DataValue::Num(Num::Int(i))) => DataValue::from(i as f64),
+            (ColType::Float, d) => d,
+            (_, DataValue::Num(Num::Float(f)))
+                if f.round() == f && f >= i64::MIN as f64 && f < i64::MAX as f64 =>
+            {
+                DataValue::from(f as i64)
+            }
+            (_, d) => d,
+        }
+    }
     pub(crate) fn coerce(&self, data: DataValue, cur_vld: ValidityTs) -> Result<DataValue> {
         if matches!(data, DataValue::Null) {
             return if self.nullable {
@@ -65,6 +65,25 @@ fn test_mul() {
     );
 }
 
+#[test]
+fn test_int_overflow() {
+    assert!(op_add(&[DataValue::from(i64::MAX), DataValue::from(1)]).is_err());
+    assert!(op_sub(&[DataValue::from(i64::MIN), DataValue::from(1)]).is_err());
+    assert!(op_mul(&[DataValue::from(i64::MAX), DataValue::from(2)]).is_err());
+    assert!(op_minus(&[DataValue::from(i64::MIN)]).is_err());
+    assert!(op_abs(&[DataValue::from(i64::MIN)]).is_err());
+    assert!(op_mod(&[DataValue::from(i64::MIN), DataValue::from(-1)]).is_err());
+    assert!(op_mod(&[DataValue::from(5), DataValue::from(0)]).is_err());
+    assert_eq!(
+        op_mod(&[DataValue::from(-7), DataValue::from(3)]).unwrap(),
+        DataValue::from(-1)
+    );
+    assert_eq!(
+        op_add(&[DataValue::from(i64::MAX), DataValue::from(1.0)]).unwrap(),
+        DataValue::from(i64::MAX as f64 + 1.0)
+    );
+}
+
 #[test]
 fn test_div() {
     assert_eq!(
@@ -247,6 +266,56 @@ fn test_comparators() {
     assert!(op_lt(&[DataValue::Null, DataValue::from(true)]).is_err());
 }
 
+#[test]
+fn test_nan_comparisons() {
+    let nan = DataValue::from(f64::NAN);
+    assert_eq!(
+        op_eq(&[nan.clone(), nan.clone()]).unwrap(),
+        DataValue::from(true)
+    );
+    assert_eq!(
+        op_eq(&[nan.clone(), DataValue::from(1)]).unwrap(),
+        DataValue::from(false)
+    );
+    for x in [DataValue::from(1), DataValue::from(f64::INFINITY)] {
+        assert_eq!(
+            op_lt(&[x.clone(), nan.clone()]).unwrap(),
+            DataValue::from(true)
+        );
+        assert_eq!(
+            op_le(&[x.clone(), nan.clone()]).unwrap(),
+            DataValue::from(true)
+        );
+        assert_eq!(
+            op_gt(&[nan.clone(), x.clone()]).unwrap(),
+            DataValue::from(true)
+        );
+        assert_eq!(
+            op_ge(&[nan.clone(), x.clone()]).unwrap(),
+            DataValue::from(true)
+        );
+        assert_eq!(
+            op_lt(&[nan.clone(), x.clone()]).unwrap(),
+            DataValue::from(false)
+        );
+    }
+    let neg_nan = DataValue::from(-f64::NAN);
+    for x in [DataValue::from(1), DataValue::from(f64::NEG_INFINITY)] {
+        assert_eq!(
+            op_lt(&[neg_nan.clone(), x.clone()]).unwrap(),
+            DataValue::from(true)
+        );
+        assert_eq!(
+            op_gt(&[x.clone(), neg_nan.clone()]).unwrap(),
+            DataValue::from(true)
+        );
+        assert_eq!(
+            op_ge(&[neg_nan.clone(), x.clone()]).unwrap(),
+            DataValue::from(false)
+        );
+    }
+}
+
 #[test]
 fn test_max_min() {
     assert_eq!(op_max(&[DataValue::from(1),]).unwrap(), DataValue::from(1));
@@ -647,6 +716,55 @@ fn test_str_includes() {
     );
 }
 
+#[test]
+fn test_split_join() {
+    let s = DataValue::from("a,b,,c");
+    let sep = DataValue::from(",");
+    let parts = op_split(&[s.clone(), sep.clone()]).unwrap();
+    assert_eq!(
+        parts,
+        DataValue::List(vec![
+            DataValue::from("a"),
+            DataValue::from("b"),
+            DataValue::from(""),
+            DataValue::from("c")
+        ])
+    );
+    assert_eq!(op_join(&[parts, sep.clone()]).unwrap(), s);
+    assert!(op_split(&[DataValue::from("abc"), DataValue::from("")]).is_err());
+    assert!(op_join(&[DataValue::List(vec![DataValue::from(1)]), sep.clone()]).is_err());
+    assert_eq!(
+        op_split(&[DataValue::Null, sep.clone()]).unwrap(),
+        DataValue::Null
+    );
+    assert_eq!(op_join(&[DataValue::Null, sep]).unwrap(), DataValue::Null);
+}
+
+#[test]
+fn test_format() {
+    assert_eq!(
+        op_format(&[
+            DataValue::from("{} has {} friends"),
+            DataValue::from("Alice"),
+            DataValue::from(3)
+        ])
+        .unwrap(),
+        DataValue::from("Alice has 3 friends")
+    );
+    assert_eq!(
+        op_format(&[DataValue::from("{{{}}}"), DataValue::from(1.5)]).unwrap(),
+        DataValue::from("{1.5}")
+    );
+    assert!(op_format(&[DataValue::from("{} and {}"), DataValue::from(1)]).is_err());
+    assert!(op_format(&[
+        DataValue::from("{}"),
+        DataValue::from(1),
+        DataValue::from(2)
+    ])
+    .is_err());
+    assert!(op_format(&[DataValue::from("{")]).is_err());
+}
+
 #[test]
 fn test_casings() {
     assert_eq!(
@@ -899,6 +1017,24 @@ fn test_predicates() {
     );
 }
 
+#[test]
+fn test_typeof() {
+    for (v, t) in [
+        (DataValue::Null, "Null"),
+        (DataValue::from(true), "Bool"),
+        (DataValue::from(1), "Int"),
+        (DataValue::from(1.), "Float"),
+        (DataValue::from("x"), "String"),
+        (DataValue::Bytes(vec![1]), "Bytes"),
+        (
+            DataValue::List(vec![DataValue::List(vec![DataValue::from(1)])]),
+            "List",
+        ),
+    ] {
+        assert_eq!(op_typeof(&[v]).unwrap(), DataValue::from(t));
+    }
+}
+
 #[test]
 fn test_prepend_append() {
     assert_eq!(
@@ -986,6 +1122,46 @@ fn test_sort_reverse() {
     )
 }
 
+#[test]
+fn test_hash() {
+    let v = DataValue::List(vec![DataValue::from("a"), DataValue::from(1)]);
+    assert_eq!(op_hash(&[v.clone()]).unwrap(), op_hash(&[v]).unwrap());
+    assert_eq!(
+        op_hash(&[DataValue::from(1)]).unwrap(),
+        op_hash(&[DataValue::from(1.0)]).unwrap()
+    );
+    assert_eq!(
+        op_hash(&[DataValue::List(vec![DataValue::from(2)])]).unwrap(),
+        op_hash(&[DataValue::List(vec![DataValue::from(2.0)])]).unwrap()
+    );
+    // large ints compare equal to the nearest float, so they must hash like it
+    for (i, f) in [
+        (9007199254740993, 9007199254740992.),
+        (i64::MAX, 9223372036854775808.),
+    ] {
+        assert_eq!(
+            op_eq(&[DataValue::from(i), DataValue::from(f)]).unwrap(),
+            DataValue::from(true)
+        );
+        assert_eq!(
+            op_hash(&[DataValue::from(i)]).unwrap(),
+            op_hash(&[DataValue::from(f)]).unwrap()
+        );
+    }
+    assert_eq!(
+        op_hash(&[DataValue::from(0)]).unwrap(),
+        op_hash(&[DataValue::from(-0.0)]).unwrap()
+    );
+    assert_ne!(
+        op_hash(&[DataValue::from(1)]).unwrap(),
+        op_hash(&[DataValue::from(1.5)]).unwrap()
+    );
+    assert_ne!(
+        op_hash(&[DataValue::from("a")]).unwrap(),
+        op_hash(&[DataValue::from("b")]).unwrap()
+    );
+}
+
 #[test]
 fn test_haversine() {
     let d = op_haversine_deg_input(&[
@@ -10,6 +10,8 @@
 use std::collections::{BTreeMap, HashMap};
 use std::mem::size_of;
 
+use uuid::Uuid;
+
 use crate::data::symb::Symbol;
 use crate::data::value::DataValue;
 
@@ -55,3 +57,49 @@ fn display_datavalues() {
         ])
     );
 }
+
+#[test]
+fn typed_conversions() {
+    assert_eq!(i64::try_from(DataValue::from(-3)).unwrap(), -3);
+    assert_eq!(u64::try_from(DataValue::from(3)).unwrap(), 3);
+    assert!(u64::try_from(DataValue::from(-1)).is_err());
+    assert_eq!(i64::try_from(DataValue::from(3.0)).unwrap(), 3);
+    assert!(i64::try_from(DataValue::from(1e300)).is_err());
+    assert!(i64::try_from(DataValue::from(-1e300)).is_err());
+    assert!(i64::try_from(DataValue::from(f64::INFINITY)).is_err());
+    assert!(i64::try_from(DataValue::from(f64::NAN)).is_err());
+    assert!(i64::try_from(DataValue::from(9223372036854775808.0)).is_err());
+    assert_eq!(
+        i64::try_from(DataValue::from(-9223372036854775808.0)).unwrap(),
+        i64::MIN
+    );
+    assert!(u64::try_from(DataValue::from(1e300)).is_err());
+    assert!(u64::try_from(DataValue::from(f64::INFINITY)).is_err());
+    assert_eq!(
+        DataValue::try_from(i64::MAX as u64).unwrap(),
+        DataValue::from(i64::MAX)
+    );
+    assert!(DataValue::try_from(i64::MAX as u64 + 1).is_err());
+    assert!(DataValue::try_from(u64::MAX).is_err());
+    assert_eq!(f64::try_from(DataValue::from(1.5)).unwrap(), 1.5);
+    assert!(bool::try_from(DataValue::from(true)).unwrap());
+    assert!(bool::try_from(DataValue::Null).is_err());
+    assert_eq!(
+        String::try_from(DataValue::from("abc".to_string())).unwrap(),
+        "abc"
+    );
+    assert!(String::try_from(DataValue::from(1)).is_err());
+    let id = Uuid::new_v4();
+    assert_eq!(Uuid::try_from(DataValue::from(id)).unwrap(), id);
+    let v = DataValue::from(vec![1i64, 2, 3]);
+    assert_eq!(
+        v,
+        DataValue::List(vec![
+            DataValue::from(1),
+            DataValue::from(2),
+            DataValue::from(3)
+        ])
+    );
+    assert_eq!(Vec::<i64>::try_from(v).unwrap(), vec![1, 2, 3]);
+    assert!(Vec::<i64>::try_from(DataValue::from(vec!["a"])).is_err());
+}
@@ -13,10 +13,12 @@ use std::collections::BTreeSet;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 
+use miette::{miette, Diagnostic};
 use ordered_float::OrderedFloat;
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
 use smartstring::{LazyCompact, SmartString};
+use thiserror::Error;
 use uuid::Uuid;
 
 /// UUID value in the database
@@ -158,6 +160,16 @@ impl From<i64> for DataValue {
     }
 }
 
+impl TryFrom<u64> for DataValue {
+    type Error = miette::Report;
+
+    fn try_from(v: u64) -> Result<Self, Self::Error> {
+        i64::try_from(v)
+            .map(DataValue::from)
+            .map_err(|_| miette!("Cannot convert {} into an integer value: out of range", v))
+    }
+}
+
 impl From<f64> for DataValue {
     fn from(v: f64) -> Self {
         DataValue::Num(Num::Float(v))
@@ -182,6 +194,102 @@ impl From<bool> for DataValue {
     }
 }
 
+impl From<Uuid> for DataValue {
+    fn from(v: Uuid) -> Self {
+        DataValue::uuid(v)
+    }
+}
+
+impl<T: Into<DataValue>> From<Vec<T>> for DataValue {
+    fn from(v: Vec<T>) -> Self {
+        DataValue::List(v.into_iter().map(Into::into).collect())
+    }
+}
+
+#[derive(Debug, Error, Diagnostic)]
+#[error("Cannot convert {0} into {1}")]
+#[diagnostic(code(eval::bad_value_conversion))]
+pub(crate) struct ValueConversionError(DataValue, &'static str);
+
+impl TryFrom<DataValue> for i64 {
+    type Error = miette::Report;
+
+    fn try_from(v: DataValue) -> Result<Self, Self::Error> {
+        match v {
+            DataValue::Num(Num::Int(i)) => Ok(i),
+            // `as` saturates, so floats must be checked to be integral and in range first
+            DataValue::Num(Num::Float(f))
+                if f.round() == f && f >= i64::MIN as f64 && f < i64::MAX as f64 =>
+            {
+                Ok(f as i64)
+            }
+            v => Err(ValueConversionError(v, "i64").into()),
+        }
+    }
+}
+
+impl TryFrom<DataValue> for u64 {
+    type Error = miette::Report;
+
+    fn try_from(v: DataValue) -> Result<Self, Self::Error> {
+        match i64::try_from(v.clone()) {
+            Ok(i) if i >= 0 => Ok(i as u64),
+            _ => Err(ValueConversionError(v, "u64").into()),
+        }
+    }
+}
+
+impl TryFrom<DataValue> for f64 {
+    type Error = miette::Report;
+
+    fn try_from(v: DataValue) -> Result<Self, Self::Error> {
+        v.get_float()
+            .ok_or_else(|| ValueConversionError(v, "f64").into())
+    }
+}
+
+impl TryFrom<DataValue> for bool {
+    type Error = miette::Report;
+
+    fn try_from(v: DataValue) -> Result<Self, Self::Error> {
+        v.get_bool()
+            .ok_or_else(|| ValueConversionError(v, "bool").into())
+    }
+}
+
+impl TryFrom<DataValue> for String {
+    type Error = miette::Report;
+
+    fn try_from(v: DataValue) -> Result<Self, Self::Error> {
+        match v {
+            DataValue::Str(s) => Ok(s.into()),
+            v => Err(ValueConversionError(v, "String").into()),
+        }
+    }
+}
+
+impl TryFrom<DataValue> for Uuid {
+    type Error = miette::Report;
+
+    fn try_from(v: DataValue) -> Result<Self, Self::Error> {
+        match v {
+            DataValue::Uuid(UuidWrapper(u)) => Ok(u),
+            v => Err(ValueConversionError(v, "Uuid").into()),
+        }
+    }
+}
+
+impl<T: TryFrom<DataValue, Error = miette::Report>> TryFrom<DataValue> for Vec<T> {
+    type Error = miette::Report;
+
+    fn try_from(v: DataValue) -> Result<Self, Self::Error> {
+        match v {
+            DataValue::List(l) => l.into_iter().map(T::try_from).collect(),
+            v => Err(ValueConversionError(v, "Vec").into()),
+        }
+    }
+}
+
 /// Representing a number
 #[derive(Copy, Clone, serde_derive::Deserialize, serde_derive::Serialize)]
 pub enum Num {
@@ -26,11 +26,13 @@ pub(crate) enum SysOp {
     Compact,
     ListRelation(Symbol),
     ListRelations,
+    ListIndices(Symbol),
     ListRunning,
     ListFixedRules,
     KillRunning(u64),
     Explain(Box<InputProgram>),
     RemoveRelation(Vec<Symbol>),
+    TruncateRelation(Symbol),
     RenameRelation(Vec<(Symbol, Symbol)>),
     ShowTrigger(Symbol),
     SetTriggers(Symbol, Vec<String>, Vec<String>, Vec<String>),
@@ -81,6 +83,16 @@ pub(crate) fn parse_sys(
 
             SysOp::RemoveRelation(rel)
         }
+        Rule::list_indices_op => {
+            let rels_p = inner.into_inner().next().unwrap();
+            let rel = Symbol::new(rels_p.as_str(), rels_p.extract_span());
+            SysOp::ListIndices(rel)
+        }
+        Rule::truncate_relation_op => {
+            let rels_p = inner.into_inner().next().unwrap();
+            let rel = Symbol::new(rels_p.as_str(), rels_p.extract_span());
+            SysOp::TruncateRelation(rel)
+        }
         Rule::list_relation_op => {
             let rels_p = inner.into_inner().next().unwrap();
             let rel = Symbol::new(rels_p.as_str(), rels_p.extract_span());
@@ -152,7 +152,12 @@ impl InputAtom {
         for k in args.keys() {
             ensure!(
                 fields.contains(k),
-                NamedFieldNotFound(name.to_string(), k.to_string(), span)
+                NamedFieldNotFound(
+                    name.to_string(),
+                    k.to_string(),
+                    span,
+                    format!("Available columns are: {}", stored.metadata.column_names())
+                )
             );
         }
         let mut new_args = vec![];
@@ -358,4 +363,5 @@ pub(crate) struct NamedFieldNotFound(
     pub(crate) String,
     pub(crate) String,
     #[label] pub(crate) SourceSpan,
+    #[help] pub(crate) String,
 );
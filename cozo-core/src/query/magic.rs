@@ -403,7 +403,11 @@ impl NormalFormProgram {
                                                         NamedFieldNotFound(
                                                             name.to_string(),
                                                             k.to_string(),
-                                                            *span
+                                                            *span,
+                                                            format!(
+                                                                "Available columns are: {}",
+                                                                relation.metadata.column_names()
+                                                            )
                                                         )
                                                     );
                                                 }
@@ -622,6 +622,14 @@ fn make_extractor(
             expr.clone(),
             stored.typing.clone(),
         ))
+    } else if stored.typing.nullable {
+        Ok(DataExtractor::DefaultExtractor(
+            Expr::Const {
+                val: DataValue::Null,
+                span: Default::default(),
+            },
+            stored.typing.clone(),
+        ))
     } else {
         #[derive(Debug, Error, Diagnostic)]
         #[error("cannot make extractor for column {0}")]
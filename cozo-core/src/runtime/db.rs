@@ -1141,6 +1141,27 @@ impl<'s, S: Storage<'s>> Db<S> {
                     vec![vec![DataValue::from(OK_STR)]],
                 ))
             }
+            SysOp::TruncateRelation(rel_name) => {
+                let lock = self
+                    .obtain_relation_locks(iter::once(&rel_name.name))
+                    .pop()
+                    .unwrap();
+                let _guard = lock.write().unwrap();
+                let callback_targets = self.current_callback_targets();
+                let mut callback_collector = BTreeMap::new();
+                let mut tx = self.transact_write()?;
+                let n_removed =
+                    tx.truncate_relation(&rel_name, &callback_targets, &mut callback_collector)?;
+                tx.commit_tx()?;
+                #[cfg(not(target_arch = "wasm32"))]
+                if !callback_collector.is_empty() {
+                    self.send_callbacks(callback_collector)
+                }
+                Ok(NamedRows::new(
+                    vec!["n_removed".to_string()],
+                    vec![vec![DataValue::from(n_removed as i64)]],
+                ))
+            }
             SysOp::CreateIndex(rel_name, idx_name, cols) => {
                 let lock = self
                     .obtain_relation_locks(iter::once(&rel_name.name))
@@ -1170,6 +1191,7 @@ impl<'s, S: Storage<'s>> Db<S> {
                 ))
             }
             SysOp::ListRelation(rs) => self.list_relation(&rs),
+            SysOp::ListIndices(rs) => self.list_indices(&rs),
             SysOp::RenameRelation(rename_pairs) => {
                 let rel_names = rename_pairs.iter().flat_map(|(f, t)| [&f.name, &t.name]);
                 let locks = self.obtain_relation_locks(rel_names);
@@ -1531,6 +1553,28 @@ impl<'s, S: Storage<'s>> Db<S> {
             rows,
         ))
     }
+    fn list_indices(&'s self, name: &str) -> Result<NamedRows> {
+        let mut tx = self.transact()?;
+        let handle = tx.get_relation(name, false)?;
+        tx.commit_tx()?;
+        let rows = handle
+            .indices
+            .iter()
+            .map(|(idx_name, (idx_handle, _))| {
+                let cols = idx_handle
+                    .metadata
+                    .keys
+                    .iter()
+                    .map(|col| DataValue::from(&col.name as &str))
+                    .collect_vec();
+                vec![DataValue::from(idx_name as &str), DataValue::List(cols)]
+            })
+            .collect_vec();
+        Ok(NamedRows::new(
+            vec!["name".to_string(), "columns".to_string()],
+            rows,
+        ))
+    }
     fn list_relations(&'s self) -> Result<NamedRows> {
         let lower = vec![DataValue::from("")].encode_as_key(RelationId::SYSTEM);
         let upper =
@@ -6,7 +6,7 @@
  * You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Display, Formatter};
 use std::sync::atomic::Ordering;
 
@@ -25,6 +25,7 @@ use crate::data::tuple::{decode_tuple_from_key, Tuple, TupleT, ENCODED_KEY_MIN_L
 use crate::data::value::{DataValue, ValidityTs};
 use crate::parse::SourceSpan;
 use crate::query::compile::IndexPositionUse;
+use crate::runtime::callback::{CallbackCollector, CallbackOp};
 use crate::runtime::transact::SessionTx;
 use crate::{NamedRows, StoreTx};
 
@@ -215,11 +216,21 @@ impl RelationHandle {
             }
         );
         let mut ret = self.encode_key_prefix(len);
-        for val in &tuple[0..len] {
-            ret.encode_datavalue(val);
+        for val in self.canonicalize_key(&tuple[0..len]) {
+            ret.encode_datavalue(&val);
         }
         Ok(ret)
     }
+    fn canonicalize_key(&self, tuple: &[DataValue]) -> Tuple {
+        tuple
+            .iter()
+            .enumerate()
+            .map(|(i, val)| match self.metadata.keys.get(i) {
+                Some(col) => col.typing.canonicalize_key(val.clone()),
+                None => val.clone(),
+            })
+            .collect()
+    }
     pub(crate) fn encode_val_for_store(&self, tuple: &Tuple, _span: SourceSpan) -> Result<Vec<u8>> {
         let start = self.metadata.keys.len();
         let len = self.metadata.non_keys.len();
@@ -330,7 +341,7 @@ impl RelationHandle {
     }
 
     pub(crate) fn get(&self, tx: &SessionTx<'_>, key: &[DataValue]) -> Result<Option<Tuple>> {
-        let key_data = key.encode_as_key(self.id);
+        let key_data = self.canonicalize_key(key).encode_as_key(self.id);
         if self.is_temp {
             Ok(tx
                 .temp_store_tx
@@ -345,7 +356,7 @@ impl RelationHandle {
     }
 
     pub(crate) fn exists(&self, tx: &SessionTx<'_>, key: &[DataValue]) -> Result<bool> {
-        let key_data = key.encode_as_key(self.id);
+        let key_data = self.canonicalize_key(key).encode_as_key(self.id);
         if self.is_temp {
             tx.temp_store_tx.exists(&key_data, false)
         } else {
@@ -358,7 +369,7 @@ impl RelationHandle {
         tx: &'a SessionTx<'_>,
         prefix: &Tuple,
     ) -> impl Iterator<Item = Result<Tuple>> + 'a {
-        let mut lower = prefix.clone();
+        let mut lower = self.canonicalize_key(prefix);
         lower.truncate(self.metadata.keys.len());
         let mut upper = lower.clone();
         upper.push(DataValue::Bot);
@@ -379,7 +390,7 @@ impl RelationHandle {
         prefix: &Tuple,
         valid_at: ValidityTs,
     ) -> impl Iterator<Item = Result<Tuple>> + 'a {
-        let mut lower = prefix.clone();
+        let mut lower = self.canonicalize_key(prefix);
         lower.truncate(self.metadata.keys.len());
         let mut upper = lower.clone();
         upper.push(DataValue::Bot);
@@ -403,8 +414,10 @@ impl RelationHandle {
     ) -> impl Iterator<Item = Result<Tuple>> + 'a {
         let mut lower_t = prefix.clone();
         lower_t.extend_from_slice(lower);
+        let lower_t = self.canonicalize_key(&lower_t);
         let mut upper_t = prefix.clone();
         upper_t.extend_from_slice(upper);
+        let mut upper_t = self.canonicalize_key(&upper_t);
         upper_t.push(DataValue::Bot);
         let lower_encoded = lower_t.encode_as_key(self.id);
         let upper_encoded = upper_t.encode_as_key(self.id);
@@ -425,8 +438,10 @@ impl RelationHandle {
     ) -> impl Iterator<Item = Result<Tuple>> + 'a {
         let mut lower_t = prefix.clone();
         lower_t.extend_from_slice(lower);
+        let lower_t = self.canonicalize_key(&lower_t);
         let mut upper_t = prefix.clone();
         upper_t.extend_from_slice(upper);
+        let mut upper_t = self.canonicalize_key(&upper_t);
         upper_t.push(DataValue::Bot);
         let lower_encoded = lower_t.encode_as_key(self.id);
         let upper_encoded = upper_t.encode_as_key(self.id);
@@ -606,6 +621,94 @@ impl<'a> SessionTx<'a> {
         let upper_bound = Tuple::default().encode_as_key(store.id.next());
         Ok((lower_bound, upper_bound))
     }
+    /// Removes every row of the relation and its indices. Removed rows are reported
+    /// to callbacks as for `:rm`. Relations with `rm` triggers cannot be truncated.
+    pub(crate) fn truncate_relation(
+        &mut self,
+        name: &Symbol,
+        callback_targets: &BTreeSet<SmartString<LazyCompact>>,
+        callback_collector: &mut CallbackCollector,
+    ) -> Result<usize> {
+        if name.name.starts_with('_') {
+            bail!("Cannot truncate temp relation");
+        }
+        let store = self.get_relation(name, true)?;
+        if store.access_level < AccessLevel::Normal {
+            bail!(InsufficientAccessLevel(
+                store.name.to_string(),
+                "relation truncation".to_string(),
+                store.access_level
+            ))
+        }
+        if !store.rm_triggers.is_empty() {
+            #[derive(Debug, Error, Diagnostic)]
+            #[error("Cannot truncate relation {0} as it has rm triggers")]
+            #[diagnostic(code(tx::truncate_with_rm_triggers))]
+            #[diagnostic(help(
+                "Truncation does not run triggers. Remove the rm triggers first, or delete the rows with :rm"
+            ))]
+            struct TruncateWithRmTriggers(String);
+
+            bail!(TruncateWithRmTriggers(store.name.to_string()))
+        }
+
+        for (idx_handle, _) in store.indices.values() {
+            self.delete_all_rows(idx_handle, false)?;
+        }
+        let is_callback_target = callback_targets.contains(&store.name);
+        let (n_removed, old_tuples) = self.delete_all_rows(&store, is_callback_target)?;
+
+        if is_callback_target && n_removed > 0 {
+            let n_keys = store.metadata.keys.len();
+            let k_headers = store
+                .metadata
+                .keys
+                .iter()
+                .map(|k| k.name.to_string())
+                .collect_vec();
+            let mut kv_headers = k_headers.clone();
+            kv_headers.extend(store.metadata.non_keys.iter().map(|k| k.name.to_string()));
+            let new_tuples = old_tuples
+                .iter()
+                .map(|tup| tup[..n_keys].to_vec())
+                .collect_vec();
+            callback_collector
+                .entry(store.name.clone())
+                .or_default()
+                .push((
+                    CallbackOp::Rm,
+                    NamedRows::new(k_headers, new_tuples),
+                    NamedRows::new(kv_headers, old_tuples),
+                ));
+        }
+
+        Ok(n_removed)
+    }
+    /// Deletes all rows of `handle`, returning their number. Only keys are held in memory
+    /// unless `collect_rows` is set, in which case the decoded rows are returned as well.
+    fn delete_all_rows(
+        &mut self,
+        handle: &RelationHandle,
+        collect_rows: bool,
+    ) -> Result<(usize, Vec<Tuple>)> {
+        let lower_bound = Tuple::default().encode_as_key(handle.id);
+        let upper_bound = Tuple::default().encode_as_key(handle.id.next());
+        let mut rows = vec![];
+        let keys: Vec<_> = self
+            .store_tx
+            .range_scan(&lower_bound, &upper_bound)
+            .map_ok(|(k, v)| {
+                if collect_rows {
+                    rows.push(decode_tuple_from_kv(&k, &v));
+                }
+                k
+            })
+            .try_collect()?;
+        for key in &keys {
+            self.store_tx.del(key)?;
+        }
+        Ok((keys.len(), rows))
+    }
     pub(crate) fn set_access_level(&mut self, rel: Symbol, level: AccessLevel) -> Result<()> {
         let mut meta = self.get_relation(&rel, true)?;
         meta.access_level = level;
@@ -656,12 +759,16 @@ impl<'a> SessionTx<'a> {
             #[derive(Debug, Error, Diagnostic)]
             #[error("column {0} in index {1} for relation {2} not found")]
             #[diagnostic(code(tx::col_in_idx_not_found))]
-            pub(crate) struct ColInIndexNotFound(String, String, String);
+            pub(crate) struct ColInIndexNotFound(String, String, String, #[help] String);
 
             bail!(ColInIndexNotFound(
                 col.name.to_string(),
                 idx_name.name.to_string(),
-                rel_name.name.to_string()
+                rel_name.name.to_string(),
+                format!(
+                    "Available columns are: {}",
+                    rel_handle.metadata.column_names()
+                )
             ));
         }
 
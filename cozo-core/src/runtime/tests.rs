@@ -152,6 +152,45 @@ grandparent[gcld, gp] := parent[gcld, p], parent[p, gp]
     assert_eq!(res[0][0], DataValue::from("jakob"))
 }
 
+#[test]
+fn comments_in_scripts() {
+    let db = new_cozo_mem().unwrap();
+    let res = db
+        .run_script(
+            r##"
+        # leading line comment
+        // another leading line comment
+        /* block comment
+           spanning lines */
+        ?[a, b] := a = 1, /* inline */ b = "# not // a /* comment */" // trailing
+        "##,
+            Default::default(),
+        )
+        .unwrap()
+        .rows;
+    assert_eq!(
+        res,
+        vec![vec![
+            DataValue::from(1),
+            DataValue::from("# not // a /* comment */")
+        ]]
+    );
+    let res = db
+        .run_script(
+            r#"?[a, b] := a = ___"http://example.com # /* x"___, b = 1 // trailing"#,
+            Default::default(),
+        )
+        .unwrap()
+        .rows;
+    assert_eq!(
+        res,
+        vec![vec![
+            DataValue::from("http://example.com # /* x"),
+            DataValue::from(1)
+        ]]
+    );
+}
+
 #[test]
 fn default_columns() {
     let db = new_cozo_mem().unwrap();
@@ -174,6 +213,322 @@ fn default_columns() {
     .unwrap();
 }
 
+#[test]
+fn list_indices() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(":create person {id => name, age}", Default::default())
+        .unwrap();
+    db.run_script("::index create person:by_name {name}", Default::default())
+        .unwrap();
+    db.run_script(
+        "::index create person:by_age {age, name}",
+        Default::default(),
+    )
+    .unwrap();
+    let res = db
+        .run_script("::indices person", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(
+        res["rows"],
+        json!([
+            ["by_age", ["age", "name", "id"]],
+            ["by_name", ["name", "id"]]
+        ])
+    );
+    assert!(db
+        .run_script("::indices nonexistent", Default::default())
+        .is_err());
+}
+
+#[test]
+fn truncate_relation() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(
+        r#"
+        ?[k, v] <- [[1, 'a'], [2, 'b'], [3, 'c']]
+        :create kv {k => v}
+        "#,
+        Default::default(),
+    )
+    .unwrap();
+    db.run_script("::index create kv:v {v}", Default::default())
+        .unwrap();
+    let res = db
+        .run_script("::truncate kv", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([[3]]));
+    let res = db
+        .run_script("?[k, v] := *kv{k, v}", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([]));
+    let res = db
+        .run_script("?[k, v] := *kv:v{k, v}", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([]));
+    db.run_script("?[k, v] <- [[4, 'd']] :put kv {k => v}", Default::default())
+        .unwrap();
+    let res = db
+        .run_script("?[k, v] := *kv{k, v}", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([[4, "d"]]));
+}
+
+#[test]
+fn truncate_relation_with_rm_triggers() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(
+        r#"
+        ?[k, v] <- [[1, 'a']]
+        :create kv {k => v}
+        "#,
+        Default::default(),
+    )
+    .unwrap();
+    db.run_script(":create kv_log {k}", Default::default())
+        .unwrap();
+    db.run_script(
+        r#"
+        ::set_triggers kv
+        on rm {
+            ?[k] := _old[k, v]
+            :put kv_log {k}
+        }
+        "#,
+        Default::default(),
+    )
+    .unwrap();
+    assert!(db.run_script("::truncate kv", Default::default()).is_err());
+    let res = db
+        .run_script("?[k, v] := *kv{k, v}", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([[1, "a"]]));
+}
+
+#[test]
+fn truncate_relation_callbacks() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(
+        r#"
+        ?[k, v] <- [[1, 'a'], [2, 'b']]
+        :create kv {k => v}
+        "#,
+        Default::default(),
+    )
+    .unwrap();
+    let (_id, receiver) = db.register_callback("kv", None);
+    db.run_script("::truncate kv", Default::default()).unwrap();
+    std::thread::sleep(Duration::from_secs_f64(0.01));
+    let (op, new, old) = receiver.try_recv().unwrap();
+    assert_eq!(op, CallbackOp::Rm);
+    assert_eq!(new.headers, vec!["k".to_string()]);
+    assert_eq!(
+        new.rows,
+        vec![vec![DataValue::from(1)], vec![DataValue::from(2)]]
+    );
+    assert_eq!(old.headers, vec!["k".to_string(), "v".to_string()]);
+    assert_eq!(
+        old.rows,
+        vec![
+            vec![DataValue::from(1), DataValue::from("a")],
+            vec![DataValue::from(2), DataValue::from("b")]
+        ]
+    );
+    assert!(receiver.try_recv().is_err());
+}
+
+#[test]
+fn numeric_key_lookup_policy() {
+    // key lookups agree with `==`: 1 and 1.0 find the same row whatever the column type
+    let db = new_cozo_mem().unwrap();
+    for (name, typing) in [("ints", ": Int"), ("floats", ": Float"), ("anys", "")] {
+        db.run_script(
+            &format!("?[k, v] <- [[1.0, 'a']] :create {name} {{k{typing} => v}}"),
+            Default::default(),
+        )
+        .unwrap();
+        db.run_script(
+            &format!("?[k, v] <- [[1, 'b']] :put {name} {{k => v}}"),
+            Default::default(),
+        )
+        .unwrap();
+        for k in ["1", "1.0"] {
+            let res = db
+                .run_script(&format!("?[v] := *{name}{{k: {k}, v}}"), Default::default())
+                .unwrap()
+                .into_json();
+            assert_eq!(res["rows"], json!([["b"]]), "{name} {k}");
+            let res = db
+                .run_script(
+                    &format!("?[v] := *{name}{{k, v}}, k >= {k}, k <= {k}"),
+                    Default::default(),
+                )
+                .unwrap()
+                .into_json();
+            assert_eq!(res["rows"], json!([["b"]]), "{name} {k}");
+        }
+    }
+    let res = db
+        .run_script("?[k] := *anys{k}", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([[1]]));
+    let res = db
+        .run_script("?[k] := *floats{k}", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([[1.0]]));
+}
+
+#[test]
+fn nan_keys_round_trip() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(
+        r#"
+        ?[k, v] <- [[1.0, 'a'], [to_float('NAN'), 'b'], [2.0, 'c']]
+        :create nums {k: Float => v: String}
+        "#,
+        Default::default(),
+    )
+    .unwrap();
+    let res = db
+        .run_script("?[k] := *nums{k}", Default::default())
+        .unwrap()
+        .rows;
+    assert_eq!(
+        res,
+        vec![
+            vec![DataValue::from(1.0)],
+            vec![DataValue::from(2.0)],
+            vec![DataValue::from(f64::NAN)]
+        ]
+    );
+    let res = db
+        .run_script(
+            "?[v] := k = to_float('NAN'), *nums{k, v}",
+            Default::default(),
+        )
+        .unwrap()
+        .rows;
+    assert_eq!(res, vec![vec![DataValue::from("b")]]);
+}
+
+#[test]
+fn list_column_defaults() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(
+        ":create tagged {id: Int => tags: [String] default [], scores: [Int; 2] default [0, 0]}",
+        Default::default(),
+    )
+    .unwrap();
+    db.run_script("?[id] <- [[1]] :put tagged {id}", Default::default())
+        .unwrap();
+    let res = db
+        .run_script(
+            "?[id, tags, scores] := *tagged{id, tags, scores}",
+            Default::default(),
+        )
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([[1, [], [0, 0]]]));
+}
+
+#[test]
+fn nullable_columns_default_to_null() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(
+        ":create status {uid => mood: String?, quitted: Bool}",
+        Default::default(),
+    )
+    .unwrap();
+    db.run_script(
+        "?[uid, quitted] <- [['z', true]] :put status {uid => quitted}",
+        Default::default(),
+    )
+    .unwrap();
+    let res = db
+        .run_script(
+            "?[uid, mood, quitted] := *status{uid, mood, quitted}",
+            Default::default(),
+        )
+        .unwrap()
+        .rows;
+    assert_eq!(
+        res,
+        vec![vec![
+            DataValue::from("z"),
+            DataValue::Null,
+            DataValue::from(true)
+        ]]
+    );
+    assert!(db
+        .run_script(
+            "?[uid, mood] <- [['z', 'x']] :put status {uid => mood}",
+            Default::default(),
+        )
+        .is_err());
+
+    // :ensure compares an omitted nullable column against null, as it does for defaults
+    db.run_script(
+        "?[uid, quitted] <- [['z', true]] :ensure status {uid => quitted}",
+        Default::default(),
+    )
+    .unwrap();
+    db.run_script(
+        "?[uid, mood, quitted] <- [['y', 'x', true]] :put status {uid => mood, quitted}",
+        Default::default(),
+    )
+    .unwrap();
+    assert!(db
+        .run_script(
+            "?[uid, quitted] <- [['y', true]] :ensure status {uid => quitted}",
+            Default::default(),
+        )
+        .is_err());
+}
+
+#[test]
+fn unknown_column_lists_available_columns() {
+    let db = new_cozo_mem().unwrap();
+    db.run_script(":create status {uid => mood, tags}", Default::default())
+        .unwrap();
+    let err = db
+        .run_script(
+            "?[uid, mod] <- [[1, 2]] :put status {uid => mod}",
+            Default::default(),
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "required column mod not found");
+    let help = err.help().unwrap().to_string();
+    assert!(help.contains("mood"));
+    assert!(help.contains("tags"));
+
+    let err = db
+        .run_script("?[uid] := *status{uid, bogus}", Default::default())
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "stored relation 'status' does not have field 'bogus'"
+    );
+    assert_eq!(
+        err.help().unwrap().to_string(),
+        "Available columns are: uid, mood, tags"
+    );
+
+    let err = db
+        .run_script("::index create status:idx {bogus}", Default::default())
+        .unwrap_err();
+    assert_eq!(
+        err.help().unwrap().to_string(),
+        "Available columns are: uid, mood, tags"
+    );
+}
+
 #[test]
 fn rm_does_not_need_all_keys() {
     let db = new_cozo_mem().unwrap();
@@ -738,3 +1093,112 @@ fn test_multi_tx() {
     tx.abort().unwrap();
     assert!(db.run_script("?[a] := *a[a]", Default::default()).is_err());
 }
+
+#[cfg(feature = "storage-rocksdb")]
+fn rocksdb_test_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("cozo-test-{}-{}", name, uuid::Uuid::new_v4()))
+}
+
+#[cfg(feature = "storage-rocksdb")]
+fn rocksdb_fill_kv(db: &crate::Db<crate::RocksDbStorage>, n: usize) {
+    let rows = (0..n).map(|i| format!("[{i}, 'value {i}']")).join(", ");
+    db.run_script(
+        &format!("?[k, v] <- [{rows}] :create kv {{k => v}}"),
+        Default::default(),
+    )
+    .unwrap();
+}
+
+#[cfg(feature = "storage-rocksdb")]
+#[test]
+fn rocksdb_rejects_unknown_storage_version() {
+    use crate::runtime::db::DbManifest;
+
+    let path = rocksdb_test_path("storage-version");
+    std::fs::create_dir_all(&path).unwrap();
+    std::fs::write(
+        path.join("manifest"),
+        rmp_serde::to_vec_named(&DbManifest {
+            storage_version: 1000,
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    let err = crate::new_cozo_rocksdb(&path).err().unwrap();
+    assert!(err.to_string().contains("Unsupported storage version 1000"));
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[cfg(feature = "storage-rocksdb")]
+#[test]
+fn rocksdb_flush_writes_sst_files() {
+    let path = rocksdb_test_path("flush");
+    let db = crate::new_cozo_rocksdb(&path).unwrap();
+    rocksdb_fill_kv(&db, 1000);
+    db.db.flush().unwrap();
+    let has_sst = std::fs::read_dir(path.join("data"))
+        .unwrap()
+        .any(|entry| entry.unwrap().path().extension() == Some("sst".as_ref()));
+    assert!(has_sst);
+    drop(db);
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[cfg(feature = "storage-rocksdb")]
+#[test]
+fn rocksdb_memory_usage() {
+    let path = rocksdb_test_path("memory-usage");
+    let db = crate::new_cozo_rocksdb(&path).unwrap();
+    rocksdb_fill_kv(&db, 1000);
+    let stats = db.db.memory_usage();
+    assert!(stats.memtable_bytes > 0);
+    drop(db);
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[cfg(feature = "storage-rocksdb")]
+#[test]
+fn rocksdb_relation_size() {
+    let path = rocksdb_test_path("relation-size");
+    let db = crate::new_cozo_rocksdb(&path).unwrap();
+    rocksdb_fill_kv(&db, 1000);
+    db.db.flush().unwrap();
+    assert!(db.relation_size("kv").unwrap() > 0);
+    assert!(db.relation_size("missing").is_err());
+    drop(db);
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[cfg(feature = "storage-rocksdb")]
+#[test]
+fn rocksdb_custom_options() {
+    let path = rocksdb_test_path("custom-options");
+    let db = crate::new_cozo_rocksdb_with_options(&path, |builder| {
+        builder
+            .optimize_universal_style_compaction(true)
+            .wal_retention(60, 16)
+    })
+    .unwrap();
+    rocksdb_fill_kv(&db, 10);
+    let options = std::fs::read_dir(path.join("data"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|p| {
+            p.file_name()
+                .unwrap()
+                .to_string_lossy()
+                .starts_with("OPTIONS-")
+        })
+        .map(|p| std::fs::read_to_string(p).unwrap())
+        .unwrap();
+    assert!(options.contains("compaction_style=kCompactionStyleUniversal"));
+    assert!(options.contains("WAL_ttl_seconds=60"));
+    assert!(options.contains("WAL_size_limit_MB=16"));
+    let res = db
+        .run_script("?[count(k)] := *kv{k}", Default::default())
+        .unwrap()
+        .into_json();
+    assert_eq!(res["rows"], json!([[10]]));
+    drop(db);
+    std::fs::remove_dir_all(&path).unwrap();
+}
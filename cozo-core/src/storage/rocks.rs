@@ -10,11 +10,13 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use log::info;
-use miette::{miette, IntoDiagnostic, Result, WrapErr};
+use miette::{bail, miette, Diagnostic, IntoDiagnostic, Result, WrapErr};
+use thiserror::Error;
 
-use cozorocks::{DbBuilder, DbIter, RocksDb, Tx};
+pub use cozorocks::{DbBuilder, MemoryStats};
+use cozorocks::{DbIter, RocksDb, Tx};
 
-use crate::data::tuple::{check_key_for_validity, Tuple};
+use crate::data::tuple::{check_key_for_validity, Tuple, TupleT};
 use crate::data::value::ValidityTs;
 use crate::runtime::db::{BadDbInit, DbManifest};
 use crate::runtime::relation::{decode_tuple_from_kv, extend_tuple_from_v};
@@ -25,12 +27,32 @@ use crate::Db;
 const KEY_PREFIX_LEN: usize = 9;
 const CURRENT_STORAGE_VERSION: u64 = 1;
 
+#[derive(Debug, Error, Diagnostic)]
+#[error("Unsupported storage version {found}, this build supports version {supported}")]
+#[diagnostic(code(db::incompatible_storage_version))]
+#[diagnostic(help("The database may have been created by a different version of Cozo"))]
+pub(crate) struct IncompatibleStorageVersion {
+    found: u64,
+    supported: u64,
+}
+
 /// Creates a RocksDB database object.
 /// This is currently the fastest persistent storage and it can
 /// sustain huge concurrency.
 /// Supports concurrent readers and writers.
 pub fn new_cozo_rocksdb(path: impl AsRef<Path>) -> Result<Db<RocksDbStorage>> {
-    let builder = DbBuilder::default().path(path.as_ref());
+    new_cozo_rocksdb_with_options(path, |builder| builder)
+}
+
+/// Creates a RocksDB database object, letting `configure` adjust the RocksDB options,
+/// for example to enable universal-style compaction or WAL retention.
+/// The path, prefix extractor and bloom filter settings Cozo relies on are applied
+/// after `configure` and cannot be overridden.
+pub fn new_cozo_rocksdb_with_options(
+    path: impl AsRef<Path>,
+    configure: impl FnOnce(DbBuilder) -> DbBuilder,
+) -> Result<Db<RocksDbStorage>> {
+    let builder = configure(DbBuilder::default()).path(path.as_ref());
     fs::create_dir_all(path.as_ref()).map_err(|err| {
         BadDbInit(format!(
             "cannot create directory {}: {}",
@@ -52,11 +74,12 @@ pub fn new_cozo_rocksdb(path: impl AsRef<Path>) -> Result<Db<RocksDbStorage>> {
             )
             .into_diagnostic()
             .wrap_err_with(|| "when reading manifest")?;
-            assert_eq!(
-                existing.storage_version, CURRENT_STORAGE_VERSION,
-                "Unknown storage version {}",
-                existing.storage_version
-            );
+            if existing.storage_version != CURRENT_STORAGE_VERSION {
+                bail!(IncompatibleStorageVersion {
+                    found: existing.storage_version,
+                    supported: CURRENT_STORAGE_VERSION,
+                });
+            }
 
             false
         } else {
@@ -120,6 +143,35 @@ impl RocksDbStorage {
     pub(crate) fn new(db: RocksDb) -> Self {
         Self { db }
     }
+    /// Flushes the memtables to SST files.
+    pub fn flush(&self) -> Result<()> {
+        self.db.flush().into_diagnostic()
+    }
+    /// Flushes the write-ahead log, and also syncs it to disk if `sync` is true.
+    pub fn flush_wal(&self, sync: bool) -> Result<()> {
+        self.db.flush_wal(sync).into_diagnostic()
+    }
+    /// Approximate memory held by the memtables, the block cache and the table readers.
+    pub fn memory_usage(&self) -> MemoryStats {
+        self.db.approximate_memory_usage()
+    }
+}
+
+impl Db<RocksDbStorage> {
+    /// Approximate size in bytes of the rows of a stored relation, not counting its indices.
+    pub fn relation_size(&self, relation: &str) -> Result<u64> {
+        if relation.starts_with('_') {
+            bail!("Temp relation '{}' is not stored in RocksDB", relation);
+        }
+        let tx = self.transact()?;
+        let handle = tx.get_relation(relation, false)?;
+        let lower = Tuple::default().encode_as_key(handle.id);
+        let upper = Tuple::default().encode_as_key(handle.id.next());
+        self.db
+            .db
+            .approximate_size(&lower, &upper)
+            .into_diagnostic()
+    }
 }
 
 impl Storage<'_> for RocksDbStorage {
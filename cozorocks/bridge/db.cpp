@@ -95,8 +95,20 @@ shared_ptr <RocksDbBridge> open_db(const DbOpts &opts, RocksDbStatus &status) {
     if (opts.optimize_level_style_compaction) {
         options.OptimizeLevelStyleCompaction();
     }
+    if (opts.optimize_universal_style_compaction) {
+        options.OptimizeUniversalStyleCompaction();
+    }
     options.create_if_missing = opts.create_if_missing;
     options.paranoid_checks = opts.paranoid_checks;
+    if (opts.wal_ttl_seconds > 0) {
+        options.WAL_ttl_seconds = opts.wal_ttl_seconds;
+    }
+    if (opts.wal_size_limit_mb > 0) {
+        options.WAL_size_limit_MB = opts.wal_size_limit_mb;
+    }
+    if (opts.manual_wal_flush) {
+        options.manual_wal_flush = true;
+    }
     if (opts.enable_blob_files) {
         options.enable_blob_files = true;
 
@@ -135,6 +147,15 @@ shared_ptr <RocksDbBridge> open_db(const DbOpts &opts, RocksDbStatus &status) {
     return db;
 }
 
+MemoryStats RocksDbBridge::approximate_memory_usage() const {
+    MemoryStats stats{};
+    auto cf = db->DefaultColumnFamily();
+    db->GetIntProperty(cf, DB::Properties::kCurSizeAllMemTables, &stats.memtable_bytes);
+    db->GetIntProperty(cf, DB::Properties::kBlockCacheUsage, &stats.block_cache_bytes);
+    db->GetIntProperty(cf, DB::Properties::kEstimateTableReadersMem, &stats.table_readers_bytes);
+    return stats;
+}
+
 RocksDbBridge::~RocksDbBridge() {
     if (destroy_on_exit && (db != nullptr)) {
         cerr << "destroying database on exit: " << db_path << endl;
@@ -110,6 +110,33 @@ struct RocksDbBridge {
         write_status(s, status);
     }
 
+    uint64_t approximate_size(RustBytes start, RustBytes end, RocksDbStatus &status) const {
+        SizeApproximationOptions options;
+        options.include_memtables = true;
+        options.include_files = true;
+        auto cf = db->DefaultColumnFamily();
+        Range range(convert_slice(start), convert_slice(end));
+        uint64_t size = 0;
+        auto s = db->GetApproximateSizes(options, cf, &range, 1, &size);
+        write_status(s, status);
+        return size;
+    }
+
+    void flush(RocksDbStatus &status) const {
+        FlushOptions options;
+        options.wait = true;
+        auto cf = db->DefaultColumnFamily();
+        auto s = db->Flush(options, cf);
+        write_status(s, status);
+    }
+
+    void flush_wal(bool sync, RocksDbStatus &status) const {
+        auto s = db->FlushWAL(sync);
+        write_status(s, status);
+    }
+
+    MemoryStats approximate_memory_usage() const;
+
     DB *get_base_db() const {
         return db->GetBaseDB();
     }
@@ -66,6 +66,12 @@ struct TxBridge {
         }
     }
 
+    inline void set_lock_timeout(int64_t timeout_ms) {
+        if (p_tx_opts != nullptr) {
+            p_tx_opts->lock_timeout = timeout_ms;
+        }
+    }
+
     inline void clear_snapshot() {
         tx->ClearSnapshot();
     }
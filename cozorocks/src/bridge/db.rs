@@ -39,6 +39,7 @@ impl Default for DbOpts {
             prepare_for_bulk_load: false,
             increase_parallelism: 0,
             optimize_level_style_compaction: false,
+            optimize_universal_style_compaction: false,
             create_if_missing: false,
             paranoid_checks: true,
             enable_blob_files: false,
@@ -54,6 +55,9 @@ impl Default for DbOpts {
             fixed_prefix_extractor_len: 0,
             destroy_on_exit: false,
             block_cache_size: 0,
+            wal_ttl_seconds: 0,
+            wal_size_limit_mb: 0,
+            manual_wal_flush: false,
         }
     }
 }
@@ -79,6 +83,20 @@ impl DbBuilder {
         self.opts.optimize_level_style_compaction = val;
         self
     }
+    pub fn optimize_universal_style_compaction(mut self, val: bool) -> Self {
+        self.opts.optimize_universal_style_compaction = val;
+        self
+    }
+    pub fn wal_retention(mut self, ttl_seconds: u64, size_limit_mb: u64) -> Self {
+        self.opts.wal_ttl_seconds = ttl_seconds;
+        self.opts.wal_size_limit_mb = size_limit_mb;
+        self
+    }
+    /// Buffer WAL writes in memory until [`RocksDb::flush_wal`] is called.
+    pub fn manual_wal_flush(mut self, val: bool) -> Self {
+        self.opts.manual_wal_flush = val;
+        self
+    }
     pub fn create_if_missing(mut self, val: bool) -> Self {
         self.opts.create_if_missing = val;
         self
@@ -177,6 +195,40 @@ impl RocksDb {
             Err(status)
         }
     }
+    #[inline]
+    pub fn approximate_size(&self, lower: &[u8], upper: &[u8]) -> Result<u64, RocksDbStatus> {
+        let mut status = RocksDbStatus::default();
+        let size = self.inner.approximate_size(lower, upper, &mut status);
+        if status.is_ok() {
+            Ok(size)
+        } else {
+            Err(status)
+        }
+    }
+    #[inline]
+    pub fn flush(&self) -> Result<(), RocksDbStatus> {
+        let mut status = RocksDbStatus::default();
+        self.inner.flush(&mut status);
+        if status.is_ok() {
+            Ok(())
+        } else {
+            Err(status)
+        }
+    }
+    #[inline]
+    pub fn flush_wal(&self, sync: bool) -> Result<(), RocksDbStatus> {
+        let mut status = RocksDbStatus::default();
+        self.inner.flush_wal(sync, &mut status);
+        if status.is_ok() {
+            Ok(())
+        } else {
+            Err(status)
+        }
+    }
+    #[inline]
+    pub fn approximate_memory_usage(&self) -> MemoryStats {
+        self.inner.approximate_memory_usage()
+    }
     pub fn get_sst_writer(&self, path: &str) -> Result<SstWriter, RocksDbStatus> {
         let mut status = RocksDbStatus::default();
         let ret = self.inner.get_sst_writer(path, &mut status);
@@ -226,3 +278,70 @@ impl SstWriter {
 unsafe impl Send for RocksDb {}
 
 unsafe impl Sync for RocksDb {}
+
+#[cfg(test)]
+mod tests {
+    use crate::DbBuilder;
+
+    #[test]
+    fn universal_style_compaction_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "cozorocks-universal-compaction-{}",
+            std::process::id()
+        ));
+        let db = DbBuilder::default()
+            .path(&path)
+            .create_if_missing(true)
+            .optimize_universal_style_compaction(true)
+            .build()
+            .unwrap();
+        for i in 0u32..100 {
+            db.raw_put(&i.to_be_bytes(), b"value").unwrap();
+        }
+        db.range_compact(&0u32.to_be_bytes(), &100u32.to_be_bytes())
+            .unwrap();
+        let tx = db.transact().start();
+        assert_eq!(
+            &*tx.get(&42u32.to_be_bytes(), false).unwrap().unwrap(),
+            b"value"
+        );
+        drop(tx);
+        drop(db);
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn flush_wal_makes_buffered_writes_durable() {
+        let root = std::env::temp_dir().join(format!("cozorocks-flush-wal-{}", std::process::id()));
+        let path = root.join("live");
+        let db = DbBuilder::default()
+            .path(&path)
+            .create_if_missing(true)
+            .manual_wal_flush(true)
+            .build()
+            .unwrap();
+        db.raw_put(b"k", b"v").unwrap();
+
+        // copies of the live directory are what a crash would leave behind,
+        // as the memtable is never flushed to SST files
+        let crash_image = |name: &str| {
+            let dest = root.join(name);
+            std::fs::create_dir_all(&dest).unwrap();
+            for entry in std::fs::read_dir(&path).unwrap() {
+                let entry = entry.unwrap();
+                std::fs::copy(entry.path(), dest.join(entry.file_name())).unwrap();
+            }
+            let recovered = DbBuilder::default().path(&dest).build().unwrap();
+            let tx = recovered.transact().start();
+            let found = tx.get(b"k", false).unwrap().map(|v| v.to_vec());
+            found
+        };
+
+        assert_eq!(crash_image("before"), None);
+        db.flush_wal(true).unwrap();
+        assert_eq!(crash_image("after"), Some(b"v".to_vec()));
+
+        drop(db);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
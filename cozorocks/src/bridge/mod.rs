@@ -26,6 +26,7 @@ pub(crate) mod ffi {
         pub prepare_for_bulk_load: bool,
         pub increase_parallelism: usize,
         pub optimize_level_style_compaction: bool,
+        pub optimize_universal_style_compaction: bool,
         pub create_if_missing: bool,
         pub paranoid_checks: bool,
         pub enable_blob_files: bool,
@@ -41,6 +42,16 @@ pub(crate) mod ffi {
         pub fixed_prefix_extractor_len: usize,
         pub destroy_on_exit: bool,
         pub block_cache_size: usize,
+        pub wal_ttl_seconds: u64,
+        pub wal_size_limit_mb: u64,
+        pub manual_wal_flush: bool,
+    }
+
+    #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+    pub struct MemoryStats {
+        pub memtable_bytes: u64,
+        pub block_cache_bytes: u64,
+        pub table_readers_bytes: u64,
     }
 
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -132,6 +143,15 @@ pub(crate) mod ffi {
             upper: &[u8],
             status: &mut RocksDbStatus,
         );
+        fn approximate_size(
+            self: &RocksDbBridge,
+            start: &[u8],
+            end: &[u8],
+            status: &mut RocksDbStatus,
+        ) -> u64;
+        fn flush(self: &RocksDbBridge, status: &mut RocksDbStatus);
+        fn flush_wal(self: &RocksDbBridge, sync: bool, status: &mut RocksDbStatus);
+        fn approximate_memory_usage(self: &RocksDbBridge) -> MemoryStats;
         fn get_sst_writer(
             self: &RocksDbBridge,
             path: &str,
@@ -156,6 +176,7 @@ pub(crate) mod ffi {
         fn start(self: Pin<&mut TxBridge>);
         fn set_snapshot(self: Pin<&mut TxBridge>, val: bool);
         fn clear_snapshot(self: Pin<&mut TxBridge>);
+        fn set_lock_timeout(self: Pin<&mut TxBridge>, timeout_ms: i64);
         fn get(
             self: &TxBridge,
             key: &[u8],
@@ -53,6 +53,14 @@ impl TxBuilder {
         self.inner.pin_mut().set_snapshot(val);
         self
     }
+    /// Time in milliseconds to wait for a row lock before failing with `kTimedOut`.
+    /// A negative value uses `TransactionDBOptions::transaction_lock_timeout`
+    /// (1000 ms by default), and zero fails immediately if the lock is held.
+    #[inline]
+    pub fn lock_timeout(mut self, timeout_ms: i64) -> Self {
+        self.inner.pin_mut().set_lock_timeout(timeout_ms);
+        self
+    }
     #[inline]
     pub fn sync(mut self, val: bool) -> Self {
         set_w_opts_sync(self.inner.pin_mut().get_w_opts(), val);
@@ -189,3 +197,29 @@ impl Tx {
         .auto_prefix_mode(true)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{DbBuilder, StatusCode};
+
+    #[test]
+    fn lock_timeout_fails_blocked_writer() {
+        let path =
+            std::env::temp_dir().join(format!("cozorocks-lock-timeout-{}", std::process::id()));
+        let mut builder = DbBuilder::default().path(&path).create_if_missing(true);
+        builder.opts.destroy_on_exit = true;
+        let db = builder.build().unwrap();
+
+        let holder = db.transact().start();
+        holder.put(b"k", b"1").unwrap();
+
+        let waiter = db.transact().lock_timeout(10).start();
+        let err = waiter.put(b"k", b"2").unwrap_err();
+        assert_eq!(err.code, StatusCode::kTimedOut);
+
+        drop(waiter);
+        drop(holder);
+        drop(db);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}